@@ -0,0 +1,120 @@
+//! Auxiliary I2C-master interface, for a secondary device (e.g. a BMM150
+//! magnetometer) wired to the BMI270's aux bus.
+
+use crate::interface::{ReadData, WriteData};
+use crate::types::{AccOdr, AuxData, AxisData, Error};
+use crate::{regs, Bmi270};
+
+/// Number of bytes the BMI270 bursts from the aux device on each data-mode read.
+pub enum AuxBurstLen {
+    /// 1 byte.
+    Len1,
+    /// 2 bytes.
+    Len2,
+    /// 6 bytes.
+    Len6,
+    /// 8 bytes.
+    Len8,
+}
+
+impl AuxBurstLen {
+    fn bits(&self) -> u8 {
+        match self {
+            AuxBurstLen::Len1 => 0b00,
+            AuxBurstLen::Len2 => 0b01,
+            AuxBurstLen::Len6 => 0b10,
+            AuxBurstLen::Len8 => 0b11,
+        }
+    }
+}
+
+/// Auxiliary device configuration.
+pub struct AuxConf {
+    /// 7-bit I2C address of the device on the aux bus.
+    pub i2c_addr: u8,
+    /// Number of bytes read from the device each data-mode poll.
+    pub burst_len: AuxBurstLen,
+    /// Output data rate at which the aux device is polled in data mode.
+    pub odr: AccOdr,
+    /// Use manual register access (`aux_read`/`aux_write`) instead of
+    /// automatic data-mode polling.
+    pub manual: bool,
+}
+
+/// Maximum number of status polls for a manual aux transaction.
+const AUX_POLL_ATTEMPTS: u32 = 100;
+
+impl<DI, CommE, CsE> Bmi270<DI>
+where
+    DI: ReadData<Error = Error<CommE, CsE>> + WriteData<Error = Error<CommE, CsE>>,
+{
+    /// Configure the aux device address, burst length, polling rate and access mode.
+    pub fn configure_aux(&mut self, cfg: AuxConf) -> Result<(), Error<CommE, CsE>> {
+        self.iface.write_register(regs::AUX_DEV_ID, cfg.i2c_addr << 1)?;
+
+        let manual_bit = if cfg.manual { 0b1000_0000 } else { 0 };
+        self.iface
+            .write_register(regs::AUX_IF_CONF, manual_bit | cfg.burst_len.bits())?;
+
+        self.iface.write_register(regs::AUX_CONF, odr_bits(&cfg.odr))
+    }
+
+    /// Write a single register on the aux device (requires manual mode).
+    pub fn aux_write(&mut self, reg: u8, val: u8) -> Result<(), Error<CommE, CsE>> {
+        self.iface.write_register(regs::AUX_WR_ADDR, reg)?;
+        self.iface.write_register(regs::AUX_WR_DATA, val)?;
+        self.wait_for_aux_idle()
+    }
+
+    /// Read up to 8 bytes from the aux device starting at `reg` (requires manual mode).
+    pub fn aux_read(&mut self, reg: u8, buf: &mut [u8]) -> Result<(), Error<CommE, CsE>> {
+        self.iface.write_register(regs::AUX_RD_ADDR, reg)?;
+        self.wait_for_aux_idle()?;
+        self.iface.read_data(regs::DATA_0, buf)
+    }
+
+    /// Read the most recent aux sample from the data registers, as populated
+    /// by data-mode polling (see [`configure_aux`](Self::configure_aux)).
+    pub fn read_aux_data(&mut self) -> Result<AuxData, Error<CommE, CsE>> {
+        let mut buf = [0u8; 8];
+        self.iface.read_data(regs::DATA_0, &mut buf)?;
+        Ok(AuxData {
+            axis: AxisData {
+                x: i16::from_le_bytes([buf[0], buf[1]]),
+                y: i16::from_le_bytes([buf[2], buf[3]]),
+                z: i16::from_le_bytes([buf[4], buf[5]]),
+            },
+            r: [buf[6], buf[7]],
+        })
+    }
+
+    fn wait_for_aux_idle(&mut self) -> Result<(), Error<CommE, CsE>> {
+        for _ in 0..AUX_POLL_ATTEMPTS {
+            let status = self.iface.read_register(regs::STATUS)?;
+            if status & regs::STATUS_AUX_BUSY == 0 {
+                return Ok(());
+            }
+        }
+        Err(Error::Timeout)
+    }
+}
+
+fn odr_bits(odr: &AccOdr) -> u8 {
+    match odr {
+        AccOdr::Odr0p78 => 0x01,
+        AccOdr::Odr1p5 => 0x02,
+        AccOdr::Odr3p1 => 0x03,
+        AccOdr::Odr6p25 => 0x04,
+        AccOdr::Odr12p5 => 0x05,
+        AccOdr::Odr25 => 0x06,
+        AccOdr::Odr50 => 0x07,
+        AccOdr::Odr100 => 0x08,
+        AccOdr::Odr200 => 0x09,
+        AccOdr::Odr400 => 0x0A,
+        AccOdr::Odr800 => 0x0B,
+        AccOdr::Odr1k6 => 0x0C,
+        AccOdr::Odr3k2 => 0x0D,
+        AccOdr::Odr6k4 => 0x0E,
+        AccOdr::Odr12k8 => 0x0F,
+    }
+}
@@ -0,0 +1,144 @@
+//! Fast Offset Compensation (FOC) and accel/gyro offset register access.
+
+use crate::interface::{ReadData, WriteData};
+use crate::types::{AxisData, Error};
+use crate::{regs, Bmi270};
+
+/// Maximum number of status polls to attempt before giving up on a FOC run.
+///
+/// The device completes FOC well within this many polls; bailing out here
+/// avoids the fixed ~240 ms wait other drivers use, which can overrun a
+/// 200 ms bus timeout. The budget covers both phases of the poll: seeing
+/// `cmd_rdy` go low, then seeing it go high again.
+const FOC_POLL_ATTEMPTS: u32 = 100;
+
+/// Expected accelerometer reading on one axis while FOC runs.
+pub enum AxisFocTarget {
+    /// Axis excluded from FOC.
+    Disabled,
+    /// Axis expected to read +1 g.
+    PlusOneG,
+    /// Axis expected to read -1 g.
+    MinusOneG,
+    /// Axis expected to read 0 g.
+    ZeroG,
+}
+
+impl AxisFocTarget {
+    fn bits(&self) -> u8 {
+        match self {
+            AxisFocTarget::Disabled => 0b00,
+            AxisFocTarget::PlusOneG => 0b01,
+            AxisFocTarget::MinusOneG => 0b10,
+            AxisFocTarget::ZeroG => 0b11,
+        }
+    }
+}
+
+/// Target orientation for a Fast Offset Compensation run.
+///
+/// Gyroscope FOC has no orientation to specify: it always assumes the
+/// device is held still and every axis should read zero angular rate.
+pub struct FocTarget {
+    /// Expected accelerometer X axis reading.
+    pub x: AxisFocTarget,
+    /// Expected accelerometer Y axis reading.
+    pub y: AxisFocTarget,
+    /// Expected accelerometer Z axis reading.
+    pub z: AxisFocTarget,
+    /// Whether to calibrate the gyroscope alongside the accelerometer.
+    pub gyro: bool,
+}
+
+impl FocTarget {
+    fn conf_byte(&self) -> u8 {
+        let gyro_bit = if self.gyro { 1 } else { 0 };
+        (gyro_bit << 6) | (self.z.bits() << 4) | (self.y.bits() << 2) | self.x.bits()
+    }
+}
+
+impl<DI, CommE, CsE> Bmi270<DI>
+where
+    DI: ReadData<Error = Error<CommE, CsE>> + WriteData<Error = Error<CommE, CsE>>,
+{
+    /// Run Fast Offset Compensation with the device held still in `target`'s orientation.
+    ///
+    /// Polls `STATUS.cmd_rdy` for completion instead of sleeping a fixed
+    /// duration, returning [`Error::Timeout`] if the device never reports
+    /// completion. `cmd_rdy` goes low the moment the FOC command is
+    /// accepted and only returns high once the engine is done, so unlike
+    /// `INTERNAL_STATUS`'s steady-state init message, it actually reflects
+    /// the run in progress — but only once we've actually seen it go low:
+    /// a stale `cmd_rdy` still high from before the `CMD` write must not be
+    /// mistaken for completion, so this waits for a low reading before it
+    /// will accept a high one as done.
+    pub fn perform_foc(&mut self, target: FocTarget) -> Result<(), Error<CommE, CsE>> {
+        self.iface.write_register(regs::FOC_CONF, target.conf_byte())?;
+        self.iface.write_register(regs::CMD, regs::CMD_START_FOC)?;
+
+        let mut saw_busy = false;
+        for _ in 0..FOC_POLL_ATTEMPTS {
+            let ready = self.iface.read_register(regs::STATUS)? & regs::STATUS_CMD_RDY != 0;
+            if ready && saw_busy {
+                return Ok(());
+            }
+            saw_busy |= !ready;
+        }
+        Err(Error::Timeout)
+    }
+
+    /// Read the accelerometer offset compensation registers.
+    pub fn get_accel_offset(&mut self) -> Result<AxisData, Error<CommE, CsE>> {
+        let mut buf = [0u8; 3];
+        self.iface.read_data(regs::OFFSET_ACC_0, &mut buf)?;
+        Ok(AxisData {
+            x: i16::from(buf[0] as i8),
+            y: i16::from(buf[1] as i8),
+            z: i16::from(buf[2] as i8),
+        })
+    }
+
+    /// Write the accelerometer offset compensation registers.
+    ///
+    /// Each axis is an 8-bit signed offset; only the low byte of `offset`'s
+    /// fields is used.
+    pub fn set_accel_offset(&mut self, offset: &AxisData) -> Result<(), Error<CommE, CsE>> {
+        self.iface.write_register(regs::OFFSET_ACC_0, offset.x as u8)?;
+        self.iface.write_register(regs::OFFSET_ACC_0 + 1, offset.y as u8)?;
+        self.iface.write_register(regs::OFFSET_ACC_0 + 2, offset.z as u8)?;
+        Ok(())
+    }
+
+    /// Read the gyroscope offset compensation registers.
+    pub fn get_gyro_offset(&mut self) -> Result<AxisData, Error<CommE, CsE>> {
+        let mut lsb = [0u8; 3];
+        self.iface.read_data(regs::OFFSET_GYR_0, &mut lsb)?;
+        let en = self.iface.read_register(regs::OFFSET_GYR_EN)?;
+        let msb_x = en & 0b11;
+        let msb_y = (en >> 2) & 0b11;
+        let msb_z = (en >> 4) & 0b11;
+        Ok(AxisData {
+            x: sign_extend_10(lsb[0], msb_x),
+            y: sign_extend_10(lsb[1], msb_y),
+            z: sign_extend_10(lsb[2], msb_z),
+        })
+    }
+
+    /// Write the gyroscope offset compensation registers and enable their application.
+    pub fn set_gyro_offset(&mut self, offset: &AxisData) -> Result<(), Error<CommE, CsE>> {
+        self.iface.write_register(regs::OFFSET_GYR_0, offset.x as u8)?;
+        self.iface.write_register(regs::OFFSET_GYR_0 + 1, offset.y as u8)?;
+        self.iface.write_register(regs::OFFSET_GYR_0 + 2, offset.z as u8)?;
+        let msb_x = ((offset.x >> 8) & 0b11) as u8;
+        let msb_y = ((offset.y >> 8) & 0b11) as u8;
+        let msb_z = ((offset.z >> 8) & 0b11) as u8;
+        let en = 0b0100_0000 | (msb_z << 4) | (msb_y << 2) | msb_x;
+        self.iface.write_register(regs::OFFSET_GYR_EN, en)
+    }
+}
+
+/// Reassemble a 10-bit signed gyro offset from its LSB byte and 2-bit MSB field.
+fn sign_extend_10(lsb: u8, msb: u8) -> i16 {
+    let raw = ((msb as i16) << 8) | lsb as i16;
+    (raw << 6) >> 6
+}
@@ -0,0 +1,210 @@
+//! Feature-engine configuration for motion, step-counting and wrist
+//! interrupts, and routing of those interrupts to the two hardware pins.
+
+use crate::interface::{ReadData, WriteData};
+use crate::types::{Activity, Error, WristGesture, WristGestureActivity};
+use crate::{regs, Bmi270};
+
+/// Offset within a feature's page of its enable byte.
+const FEATURE_ENABLE_OFFSET: u8 = 0;
+/// Bit within a feature's enable byte that turns it on.
+const FEATURE_ENABLE_BIT: u8 = 0b1;
+/// Offset within a motion feature's page of its threshold, 2 bytes little-endian.
+const MOTION_THRESH_OFFSET: u8 = 2;
+/// Offset within a motion feature's page of its duration, 2 bytes little-endian.
+const MOTION_DUR_OFFSET: u8 = 4;
+
+/// A feature-engine interrupt source that can be routed to a pin.
+pub enum Feature {
+    /// Any-motion detector.
+    AnyMotion,
+    /// No-motion detector.
+    NoMotion,
+    /// Step counter watermark/step-detector.
+    StepCounter,
+    /// Wrist gesture detector.
+    WristGesture,
+}
+
+impl Feature {
+    /// Feature-engine page this feature's configuration lives on.
+    fn page(&self) -> u8 {
+        match self {
+            Feature::AnyMotion => regs::PAGE_ANY_MOTION,
+            Feature::NoMotion => regs::PAGE_NO_MOTION,
+            Feature::StepCounter => regs::PAGE_STEP_COUNTER,
+            Feature::WristGesture => regs::PAGE_WRIST_GESTURE,
+        }
+    }
+
+    /// `INT1_MAP_FEAT`/`INT2_MAP_FEAT` bit that routes this feature's
+    /// interrupt to a pin.
+    ///
+    /// This is a separate bit layout from the page-local enable bit above:
+    /// where a feature's config lives and how its interrupt is routed are
+    /// independent concerns on real hardware.
+    fn int_map_bit(&self) -> u8 {
+        match self {
+            Feature::AnyMotion => regs::INT_MAP_BIT_ANY_MOTION,
+            Feature::NoMotion => regs::INT_MAP_BIT_NO_MOTION,
+            Feature::StepCounter => regs::INT_MAP_BIT_STEP_COUNTER,
+            Feature::WristGesture => regs::INT_MAP_BIT_WRIST_GESTURE,
+        }
+    }
+}
+
+/// Which hardware interrupt pin a feature is routed to.
+pub enum IntPin {
+    /// `INT1`.
+    Int1,
+    /// `INT2`.
+    Int2,
+}
+
+/// Whether an interrupt pin signals level-sensitive until cleared, or a short pulse.
+pub enum IntLatch {
+    /// The pin stays asserted until the interrupt status is read (latched).
+    Latched,
+    /// The pin pulses briefly then deasserts on its own.
+    Pulsed,
+}
+
+/// Electrical configuration for one hardware interrupt pin.
+pub struct IntPinConf {
+    /// Open-drain (vs push-pull) output.
+    pub open_drain: bool,
+    /// Active-high (vs active-low) signalling.
+    pub active_high: bool,
+    /// Latched vs pulsed behavior.
+    ///
+    /// The BMI270 shares a single latch-mode register between both pins, so
+    /// configuring one pin's latch mode affects the other as well.
+    pub latch: IntLatch,
+}
+
+impl<DI, CommE, CsE> Bmi270<DI>
+where
+    DI: ReadData<Error = Error<CommE, CsE>> + WriteData<Error = Error<CommE, CsE>>,
+{
+    /// Enable the any-motion detector: fires once acceleration stays above
+    /// `threshold_mg` for `duration` consecutive samples.
+    pub fn enable_any_motion(
+        &mut self,
+        threshold_mg: u16,
+        duration: u16,
+    ) -> Result<(), Error<CommE, CsE>> {
+        self.write_feature_u16(regs::PAGE_ANY_MOTION, MOTION_THRESH_OFFSET, threshold_mg)?;
+        self.write_feature_u16(regs::PAGE_ANY_MOTION, MOTION_DUR_OFFSET, duration)?;
+        self.set_feature_enabled(Feature::AnyMotion, true)
+    }
+
+    /// Enable the no-motion detector: fires once acceleration stays below
+    /// `threshold_mg` for `duration` consecutive samples.
+    pub fn enable_no_motion(
+        &mut self,
+        threshold_mg: u16,
+        duration: u16,
+    ) -> Result<(), Error<CommE, CsE>> {
+        self.write_feature_u16(regs::PAGE_NO_MOTION, MOTION_THRESH_OFFSET, threshold_mg)?;
+        self.write_feature_u16(regs::PAGE_NO_MOTION, MOTION_DUR_OFFSET, duration)?;
+        self.set_feature_enabled(Feature::NoMotion, true)
+    }
+
+    /// Enable the step counter.
+    pub fn enable_step_counter(&mut self) -> Result<(), Error<CommE, CsE>> {
+        self.set_feature_enabled(Feature::StepCounter, true)
+    }
+
+    /// Read the current step count.
+    pub fn step_count(&mut self) -> Result<u16, Error<CommE, CsE>> {
+        let mut buf = [0u8; 2];
+        self.iface.read_data(regs::SC_OUT_0, &mut buf)?;
+        Ok(u16::from_le_bytes(buf))
+    }
+
+    /// Enable the wrist gesture/activity detector.
+    pub fn enable_wrist_gesture(&mut self) -> Result<(), Error<CommE, CsE>> {
+        self.set_feature_enabled(Feature::WristGesture, true)
+    }
+
+    /// Read the last decoded wrist gesture and activity state.
+    pub fn wrist_gesture_activity(&mut self) -> Result<WristGestureActivity, Error<CommE, CsE>> {
+        let raw = self.iface.read_register(regs::WR_GEST_ACT)?;
+        Ok(decode_wrist_gesture_activity(raw))
+    }
+
+    /// Route `feature`'s interrupt to `pin`.
+    pub fn map_interrupt(&mut self, feature: Feature, pin: IntPin) -> Result<(), Error<CommE, CsE>> {
+        let reg = match pin {
+            IntPin::Int1 => regs::INT1_MAP_FEAT,
+            IntPin::Int2 => regs::INT2_MAP_FEAT,
+        };
+        let current = self.iface.read_register(reg)?;
+        self.iface.write_register(reg, current | feature.int_map_bit())
+    }
+
+    /// Configure a hardware interrupt pin's electrical behavior.
+    pub fn configure_int_pin(
+        &mut self,
+        pin: IntPin,
+        cfg: IntPinConf,
+    ) -> Result<(), Error<CommE, CsE>> {
+        let reg = match pin {
+            IntPin::Int1 => regs::INT1_IO_CTRL,
+            IntPin::Int2 => regs::INT2_IO_CTRL,
+        };
+        let mut ctrl = 0b0000_1000; // output enabled
+        if cfg.open_drain {
+            ctrl |= 0b0000_0100;
+        }
+        if cfg.active_high {
+            ctrl |= 0b0000_0010;
+        }
+        self.iface.write_register(reg, ctrl)?;
+
+        let latch = match cfg.latch {
+            IntLatch::Latched => 0b0000_0001,
+            IntLatch::Pulsed => 0b0000_0000,
+        };
+        self.iface.write_register(regs::INT_LATCH, latch)
+    }
+
+    fn set_feature_enabled(&mut self, feature: Feature, enabled: bool) -> Result<(), Error<CommE, CsE>> {
+        let page = feature.page();
+        let current = self.read_feature_byte(page, FEATURE_ENABLE_OFFSET)?;
+        let updated = if enabled {
+            current | FEATURE_ENABLE_BIT
+        } else {
+            current & !FEATURE_ENABLE_BIT
+        };
+        self.write_feature_byte(page, FEATURE_ENABLE_OFFSET, updated)
+    }
+
+    fn write_feature_u16(&mut self, page: u8, offset: u8, value: u16) -> Result<(), Error<CommE, CsE>> {
+        let [lo, hi] = value.to_le_bytes();
+        self.write_feature_byte(page, offset, lo)?;
+        self.write_feature_byte(page, offset + 1, hi)
+    }
+}
+
+/// Decode the `WR_GEST_ACT` register into its gesture and activity fields.
+fn decode_wrist_gesture_activity(raw: u8) -> WristGestureActivity {
+    let wrist_gesture = match raw & 0b111 {
+        1 => WristGesture::PushArmDown,
+        2 => WristGesture::PivotUp,
+        3 => WristGesture::Shake,
+        4 => WristGesture::FlickIn,
+        5 => WristGesture::FlickOut,
+        _ => WristGesture::Unknown,
+    };
+    let activity = match (raw >> 3) & 0b11 {
+        0 => Activity::Still,
+        1 => Activity::Walking,
+        2 => Activity::Running,
+        _ => Activity::Unknown,
+    };
+    WristGestureActivity {
+        wrist_gesture,
+        activity,
+    }
+}
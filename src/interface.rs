@@ -0,0 +1,133 @@
+//! Bus-agnostic register access, shared by the I2C and SPI front-ends.
+
+use embedded_hal::blocking::i2c::{Write, WriteRead};
+use embedded_hal::blocking::spi::Transfer;
+use embedded_hal::digital::v2::OutputPin;
+
+use crate::Error;
+
+/// Read raw register bytes from the device.
+pub trait ReadData {
+    /// Error type returned on a bus failure.
+    type Error;
+    /// Read a single register.
+    fn read_register(&mut self, reg: u8) -> Result<u8, Self::Error>;
+    /// Read `data.len()` bytes starting at `reg`.
+    fn read_data(&mut self, reg: u8, data: &mut [u8]) -> Result<(), Self::Error>;
+}
+
+/// Write raw register bytes to the device.
+pub trait WriteData {
+    /// Error type returned on a bus failure.
+    type Error;
+    /// Write a single register.
+    fn write_register(&mut self, reg: u8, value: u8) -> Result<(), Self::Error>;
+}
+
+/// I2C interface wrapper.
+#[derive(Debug)]
+pub struct I2cInterface<I2C> {
+    pub(crate) i2c: I2C,
+    pub(crate) address: u8,
+}
+
+impl<I2C, E> ReadData for I2cInterface<I2C>
+where
+    I2C: WriteRead<Error = E> + Write<Error = E>,
+{
+    type Error = Error<E, ()>;
+
+    fn read_register(&mut self, reg: u8) -> Result<u8, Self::Error> {
+        let mut data = [0];
+        self.read_data(reg, &mut data)?;
+        Ok(data[0])
+    }
+
+    fn read_data(&mut self, reg: u8, data: &mut [u8]) -> Result<(), Self::Error> {
+        self.i2c
+            .write_read(self.address, &[reg], data)
+            .map_err(Error::Comm)
+    }
+}
+
+impl<I2C, E> WriteData for I2cInterface<I2C>
+where
+    I2C: Write<Error = E>,
+{
+    type Error = Error<E, ()>;
+
+    fn write_register(&mut self, reg: u8, value: u8) -> Result<(), Self::Error> {
+        self.i2c.write(self.address, &[reg, value]).map_err(Error::Comm)
+    }
+}
+
+/// SPI interface wrapper.
+#[derive(Debug)]
+pub struct SpiInterface<SPI, CS> {
+    pub(crate) spi: SPI,
+    pub(crate) cs: CS,
+}
+
+impl<SPI, CS, E, PinE> ReadData for SpiInterface<SPI, CS>
+where
+    SPI: Transfer<u8, Error = E>,
+    CS: OutputPin<Error = PinE>,
+{
+    type Error = Error<E, PinE>;
+
+    fn read_register(&mut self, reg: u8) -> Result<u8, Self::Error> {
+        let mut data = [0];
+        self.read_data(reg, &mut data)?;
+        Ok(data[0])
+    }
+
+    fn read_data(&mut self, reg: u8, data: &mut [u8]) -> Result<(), Self::Error> {
+        // The BMI270 SPI read protocol sets the MSB of the address byte and
+        // echoes a dummy byte back before the first payload byte. The
+        // register address auto-increments as long as chip select stays
+        // low, so arbitrarily large reads are chunked through a small
+        // fixed-size scratch buffer instead of one stack allocation sized
+        // to the whole transfer.
+        let mut header = [reg | 0x80, 0u8];
+        self.cs.set_low().map_err(Error::Cs)?;
+        let result = self.read_chunks(&mut header, data);
+        self.cs.set_high().map_err(Error::Cs)?;
+        result
+    }
+}
+
+impl<SPI, CS, E, PinE> SpiInterface<SPI, CS>
+where
+    SPI: Transfer<u8, Error = E>,
+    CS: OutputPin<Error = PinE>,
+{
+    fn read_chunks(&mut self, header: &mut [u8; 2], data: &mut [u8]) -> Result<(), Error<E, PinE>> {
+        self.spi.transfer(header).map_err(Error::Comm)?;
+
+        const CHUNK: usize = 32;
+        let mut scratch = [0u8; CHUNK];
+        for out in data.chunks_mut(CHUNK) {
+            let buf = &mut scratch[..out.len()];
+            self.spi.transfer(buf).map_err(Error::Comm)?;
+            out.copy_from_slice(buf);
+        }
+        Ok(())
+    }
+}
+
+impl<SPI, CS, E, PinE> WriteData for SpiInterface<SPI, CS>
+where
+    SPI: Transfer<u8, Error = E>,
+    CS: OutputPin<Error = PinE>,
+{
+    type Error = Error<E, PinE>;
+
+    fn write_register(&mut self, reg: u8, value: u8) -> Result<(), Self::Error> {
+        let mut buf = [reg & 0x7F, value];
+        self.cs.set_low().map_err(Error::Cs)?;
+        let result = self.spi.transfer(&mut buf).map_err(Error::Comm);
+        self.cs.set_high().map_err(Error::Cs)?;
+        result?;
+        Ok(())
+    }
+}
@@ -0,0 +1,111 @@
+//! A platform-agnostic driver for the Bosch BMI270 inertial measurement unit,
+//! built on `embedded-hal` traits so it runs over either I2C or SPI.
+#![no_std]
+
+mod aux;
+mod calib;
+mod feature;
+mod fifo;
+mod interface;
+mod ois;
+mod regs;
+mod remap;
+mod scale;
+pub mod types;
+
+pub use crate::aux::{AuxBurstLen, AuxConf};
+pub use crate::calib::{AxisFocTarget, FocTarget};
+pub use crate::feature::{Feature, IntLatch, IntPin, IntPinConf};
+pub use crate::fifo::{FifoFrame, FifoFrames};
+pub use crate::interface::{I2cInterface, SpiInterface};
+pub use crate::ois::{OisAccRange, OisConfig, OisLpf, OisPort};
+pub use crate::remap::{apply_remap, AxesRemap, AxisMapping, PhysicalAxis};
+pub use crate::scale::{sensor_time_us, AccRange, GyrRange};
+pub use crate::types::*;
+
+use crate::interface::{ReadData, WriteData};
+
+/// 7-bit I2C address when the `SDO` pin is pulled low.
+pub const I2C_ADDR_SDO_LOW: u8 = 0x68;
+/// 7-bit I2C address when the `SDO` pin is pulled high.
+pub const I2C_ADDR_SDO_HIGH: u8 = 0x69;
+
+/// BMI270 driver, generic over the bus interface it was constructed with.
+pub struct Bmi270<DI> {
+    iface: DI,
+    /// Accelerometer range currently programmed on the device, used to scale
+    /// raw samples to physical units.
+    acc_range: AccRange,
+    /// Gyroscope range currently programmed on the device, used to scale raw
+    /// samples to physical units.
+    gyr_range: GyrRange,
+}
+
+impl<I2C, E> Bmi270<I2cInterface<I2C>>
+where
+    I2C: embedded_hal::blocking::i2c::WriteRead<Error = E>
+        + embedded_hal::blocking::i2c::Write<Error = E>,
+{
+    /// Create a new driver instance talking to the device over I2C at `address`.
+    pub fn new_i2c(i2c: I2C, address: u8) -> Self {
+        Bmi270 {
+            iface: I2cInterface { i2c, address },
+            acc_range: AccRange::G8,
+            gyr_range: GyrRange::Dps2000,
+        }
+    }
+
+    /// Release the wrapped I2C peripheral.
+    pub fn destroy(self) -> I2C {
+        self.iface.i2c
+    }
+}
+
+impl<SPI, CS, E, PinE> Bmi270<SpiInterface<SPI, CS>>
+where
+    SPI: embedded_hal::blocking::spi::Transfer<u8, Error = E>,
+    CS: embedded_hal::digital::v2::OutputPin<Error = PinE>,
+{
+    /// Create a new driver instance talking to the device over SPI, driving `cs` as chip select.
+    pub fn new_spi(spi: SPI, cs: CS) -> Self {
+        Bmi270 {
+            iface: SpiInterface { spi, cs },
+            acc_range: AccRange::G8,
+            gyr_range: GyrRange::Dps2000,
+        }
+    }
+
+    /// Release the wrapped SPI peripheral and chip-select pin.
+    pub fn destroy(self) -> (SPI, CS) {
+        (self.iface.spi, self.iface.cs)
+    }
+}
+
+impl<DI, CommE, CsE> Bmi270<DI>
+where
+    DI: ReadData<Error = Error<CommE, CsE>> + WriteData<Error = Error<CommE, CsE>>,
+{
+    /// Read the chip ID register (expected to be `0x24` for the BMI270).
+    pub fn chip_id(&mut self) -> Result<u8, Error<CommE, CsE>> {
+        self.iface.read_register(regs::CHIP_ID)
+    }
+
+    /// Write one byte at `offset` (`0..16`) within the given feature-engine
+    /// page, bringing that page into the `FEATURES` window first.
+    pub(crate) fn write_feature_byte(
+        &mut self,
+        page: u8,
+        offset: u8,
+        value: u8,
+    ) -> Result<(), Error<CommE, CsE>> {
+        self.iface.write_register(regs::FEAT_PAGE, page)?;
+        self.iface.write_register(regs::FEATURES_WINDOW + offset, value)
+    }
+
+    /// Read one byte at `offset` (`0..16`) within the given feature-engine
+    /// page, bringing that page into the `FEATURES` window first.
+    pub(crate) fn read_feature_byte(&mut self, page: u8, offset: u8) -> Result<u8, Error<CommE, CsE>> {
+        self.iface.write_register(regs::FEAT_PAGE, page)?;
+        self.iface.read_register(regs::FEATURES_WINDOW + offset)
+    }
+}
@@ -0,0 +1,142 @@
+//! OIS (optical image stabilization) low-latency secondary read path.
+//!
+//! The BMI270's OIS port is not reachable through the primary I2C/SPI
+//! register map at all: it is wired out on its own dedicated 4-wire SPI
+//! pins and addressed over that separate bus, specifically so a
+//! camera/gimbal control loop can read it without contending with whatever
+//! is driving the primary interface. [`OisPort`] models that second bus.
+
+use embedded_hal::blocking::spi::Transfer;
+use embedded_hal::digital::v2::OutputPin;
+
+use crate::types::{AxisData, Data, Error};
+
+/// OIS accelerometer full-scale range.
+pub enum OisAccRange {
+    /// +/-2g.
+    G2,
+    /// +/-4g.
+    G4,
+    /// +/-8g.
+    G8,
+    /// +/-16g.
+    G16,
+}
+
+/// OIS low-pass filter cutoff, as a fraction of the OIS output data rate.
+pub enum OisLpf {
+    /// Filter disabled (widest bandwidth).
+    Disabled,
+    /// ODR/4 cutoff.
+    Odr4,
+    /// ODR/8 cutoff.
+    Odr8,
+    /// ODR/16 cutoff.
+    Odr16,
+}
+
+/// OIS path configuration.
+pub struct OisConfig {
+    /// Accelerometer range used by the OIS path.
+    pub acc_range: OisAccRange,
+    /// Low-pass filter cutoff applied to both accel and gyro OIS samples.
+    pub lpf: OisLpf,
+}
+
+/// OIS range and low-pass-filter configuration, on the OIS bus.
+const OIS_CONF: u8 = 0x9C;
+/// OIS accelerometer output, on the OIS bus.
+const OIS_DATA_ACC: u8 = 0x0C;
+/// OIS gyroscope output, on the OIS bus.
+const OIS_DATA_GYR: u8 = 0x12;
+
+/// A connection to the BMI270's dedicated OIS SPI port.
+///
+/// This is a second, independent SPI bus from whatever [`Bmi270`](crate::Bmi270)
+/// is constructed with — it has its own chip select and, on real hardware,
+/// is typically wired to a different SPI peripheral entirely so the
+/// high-rate OIS loop never blocks on the primary interface.
+pub struct OisPort<SPI, CS> {
+    spi: SPI,
+    cs: CS,
+}
+
+impl<SPI, CS, E, PinE> OisPort<SPI, CS>
+where
+    SPI: Transfer<u8, Error = E>,
+    CS: OutputPin<Error = PinE>,
+{
+    /// Wrap the SPI peripheral and chip-select pin wired to the OIS port.
+    pub fn new(spi: SPI, cs: CS) -> Self {
+        OisPort { spi, cs }
+    }
+
+    /// Release the wrapped SPI peripheral and chip-select pin.
+    pub fn destroy(self) -> (SPI, CS) {
+        (self.spi, self.cs)
+    }
+
+    /// Set the OIS path's accelerometer range and low-pass filter cutoff.
+    pub fn set_config(&mut self, cfg: OisConfig) -> Result<(), Error<E, PinE>> {
+        let range_bits = match cfg.acc_range {
+            OisAccRange::G2 => 0b00,
+            OisAccRange::G4 => 0b01,
+            OisAccRange::G8 => 0b10,
+            OisAccRange::G16 => 0b11,
+        };
+        let lpf_bits = match cfg.lpf {
+            OisLpf::Disabled => 0b00,
+            OisLpf::Odr4 => 0b01,
+            OisLpf::Odr8 => 0b10,
+            OisLpf::Odr16 => 0b11,
+        };
+        self.write_register(OIS_CONF, (lpf_bits << 2) | range_bits)
+    }
+
+    /// Read the latest accel/gyro sample over the OIS port, bypassing the
+    /// primary data pipeline entirely.
+    pub fn read_data(&mut self) -> Result<Data, Error<E, PinE>> {
+        let mut acc = [0u8; 6];
+        self.read_data_at(OIS_DATA_ACC, &mut acc)?;
+        let mut gyr = [0u8; 6];
+        self.read_data_at(OIS_DATA_GYR, &mut gyr)?;
+
+        Ok(Data {
+            acc: AxisData {
+                x: i16::from_le_bytes([acc[0], acc[1]]),
+                y: i16::from_le_bytes([acc[2], acc[3]]),
+                z: i16::from_le_bytes([acc[4], acc[5]]),
+            },
+            gyr: AxisData {
+                x: i16::from_le_bytes([gyr[0], gyr[1]]),
+                y: i16::from_le_bytes([gyr[2], gyr[3]]),
+                z: i16::from_le_bytes([gyr[4], gyr[5]]),
+            },
+            // The OIS port has no sensor-time counterpart; it is the fast
+            // tap specifically because it skips everything but the raw
+            // accel/gyro registers.
+            time: 0,
+        })
+    }
+
+    fn write_register(&mut self, reg: u8, value: u8) -> Result<(), Error<E, PinE>> {
+        let mut buf = [reg & 0x7F, value];
+        self.cs.set_low().map_err(Error::Cs)?;
+        let result = self.spi.transfer(&mut buf).map_err(Error::Comm);
+        self.cs.set_high().map_err(Error::Cs)?;
+        result?;
+        Ok(())
+    }
+
+    fn read_data_at(&mut self, reg: u8, data: &mut [u8]) -> Result<(), Error<E, PinE>> {
+        let mut header = [reg | 0x80, 0u8];
+        self.cs.set_low().map_err(Error::Cs)?;
+        let result = (|| {
+            self.spi.transfer(&mut header).map_err(Error::Comm)?;
+            self.spi.transfer(data).map_err(Error::Comm)?;
+            Ok(())
+        })();
+        self.cs.set_high().map_err(Error::Cs)?;
+        result
+    }
+}
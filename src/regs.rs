@@ -0,0 +1,131 @@
+//! BMI270 register map constants, shared by every feature module.
+//!
+//! Not every address here is wired up yet; the full map is kept in one
+//! place so new feature modules can reuse it instead of re-deriving
+//! addresses from the datasheet.
+#![allow(dead_code)]
+
+/// Chip ID register.
+pub(crate) const CHIP_ID: u8 = 0x00;
+/// Error register.
+pub(crate) const ERR_REG: u8 = 0x02;
+/// Status register.
+pub(crate) const STATUS: u8 = 0x03;
+/// First data register (AUX_X_LSB); accel/gyro/aux samples are read as a burst from here.
+pub(crate) const DATA_0: u8 = 0x04;
+/// Accelerometer X LSB, 12 bytes (accel + gyro) after it.
+pub(crate) const DATA_ACC: u8 = 0x0C;
+/// Gyroscope X LSB.
+pub(crate) const DATA_GYR: u8 = 0x12;
+/// Sensor time, 3 bytes little-endian.
+pub(crate) const SENSORTIME_0: u8 = 0x18;
+/// Sensor/feature event register.
+pub(crate) const EVENT: u8 = 0x1B;
+/// Interrupt/feature status, byte 0.
+pub(crate) const INT_STATUS_0: u8 = 0x1C;
+/// Interrupt/feature status, byte 1.
+pub(crate) const INT_STATUS_1: u8 = 0x1D;
+/// Step counter output, 2 bytes little-endian.
+pub(crate) const SC_OUT_0: u8 = 0x1E;
+/// Wrist gesture and activity output.
+pub(crate) const WR_GEST_ACT: u8 = 0x20;
+/// Internal status register (init/FOC/axis-remap state).
+pub(crate) const INTERNAL_STATUS: u8 = 0x21;
+/// FIFO fill level, 2 bytes little-endian.
+pub(crate) const FIFO_LENGTH_0: u8 = 0x24;
+/// FIFO data port; every byte read advances the FIFO read pointer.
+pub(crate) const FIFO_DATA: u8 = 0x26;
+/// Accelerometer ODR/bandwidth/performance config.
+pub(crate) const ACC_CONF: u8 = 0x40;
+/// Accelerometer full-scale range.
+pub(crate) const ACC_RANGE: u8 = 0x41;
+/// Gyroscope ODR/bandwidth/performance config.
+pub(crate) const GYR_CONF: u8 = 0x42;
+/// Gyroscope full-scale range.
+pub(crate) const GYR_RANGE: u8 = 0x43;
+/// Auxiliary data-mode output data rate.
+pub(crate) const AUX_CONF: u8 = 0x44;
+/// FIFO downsampling config.
+pub(crate) const FIFO_DOWNS: u8 = 0x45;
+/// FIFO watermark level, 2 bytes little-endian.
+pub(crate) const FIFO_WTM_0: u8 = 0x46;
+/// FIFO header/aux/accel/gyro enable config, byte 0.
+pub(crate) const FIFO_CONFIG_0: u8 = 0x48;
+/// FIFO header/aux/accel/gyro enable config, byte 1.
+pub(crate) const FIFO_CONFIG_1: u8 = 0x49;
+/// Auxiliary device I2C address (7-bit, left-aligned).
+pub(crate) const AUX_DEV_ID: u8 = 0x4B;
+/// Auxiliary interface config (burst length, manual/auto mode).
+pub(crate) const AUX_IF_CONF: u8 = 0x4C;
+/// Auxiliary manual-mode read address.
+pub(crate) const AUX_RD_ADDR: u8 = 0x4D;
+/// Auxiliary manual-mode write address.
+pub(crate) const AUX_WR_ADDR: u8 = 0x4E;
+/// Auxiliary manual-mode write data.
+pub(crate) const AUX_WR_DATA: u8 = 0x4F;
+/// Interrupt pin 1 electrical configuration.
+pub(crate) const INT1_IO_CTRL: u8 = 0x53;
+/// Interrupt pin 2 electrical configuration.
+pub(crate) const INT2_IO_CTRL: u8 = 0x54;
+/// Interrupt latch mode.
+pub(crate) const INT_LATCH: u8 = 0x55;
+/// Feature interrupt mapping for pin 1.
+pub(crate) const INT1_MAP_FEAT: u8 = 0x56;
+/// Feature interrupt mapping for pin 2.
+pub(crate) const INT2_MAP_FEAT: u8 = 0x57;
+/// Data-ready/FIFO interrupt mapping (shared by both pins).
+pub(crate) const INT_MAP_DATA: u8 = 0x58;
+/// Fast offset compensation configuration (per-axis target encoding).
+pub(crate) const FOC_CONF: u8 = 0x69;
+
+/// Selects which feature-engine page is mapped into the `FEATURES` window.
+///
+/// Feature-engine settings (axis remap, any/no-motion, step counter, wrist
+/// gesture, ...) are not flat registers: each lives on its own page of a
+/// shared config block, brought into the 16-byte `FEATURES` window by
+/// writing its page index here first.
+pub(crate) const FEAT_PAGE: u8 = 0x2F;
+/// Base address of the 16-byte `FEATURES` page window (`0x30..=0x3F`).
+pub(crate) const FEATURES_WINDOW: u8 = 0x30;
+
+/// Feature page: axis remap.
+pub(crate) const PAGE_AXIS_REMAP: u8 = 1;
+/// Feature page: any-motion detector.
+pub(crate) const PAGE_ANY_MOTION: u8 = 2;
+/// Feature page: no-motion detector.
+pub(crate) const PAGE_NO_MOTION: u8 = 3;
+/// Feature page: step counter.
+pub(crate) const PAGE_STEP_COUNTER: u8 = 4;
+/// Feature page: wrist gesture detector.
+pub(crate) const PAGE_WRIST_GESTURE: u8 = 5;
+
+/// `INT1_MAP_FEAT`/`INT2_MAP_FEAT` bit routing the any-motion interrupt.
+pub(crate) const INT_MAP_BIT_ANY_MOTION: u8 = 1 << 0;
+/// `INT1_MAP_FEAT`/`INT2_MAP_FEAT` bit routing the no-motion interrupt.
+pub(crate) const INT_MAP_BIT_NO_MOTION: u8 = 1 << 1;
+/// `INT1_MAP_FEAT`/`INT2_MAP_FEAT` bit routing the step-counter interrupt.
+pub(crate) const INT_MAP_BIT_STEP_COUNTER: u8 = 1 << 2;
+/// `INT1_MAP_FEAT`/`INT2_MAP_FEAT` bit routing the wrist-gesture interrupt.
+pub(crate) const INT_MAP_BIT_WRIST_GESTURE: u8 = 1 << 3;
+/// Accelerometer offset compensation, X/Y/Z, one byte each.
+pub(crate) const OFFSET_ACC_0: u8 = 0x71;
+/// Gyroscope offset compensation, X/Y/Z LSBs, one byte each.
+pub(crate) const OFFSET_GYR_0: u8 = 0x74;
+/// Gyroscope offset compensation MSBs and enable bits.
+pub(crate) const OFFSET_GYR_EN: u8 = 0x77;
+/// Power configuration (advanced power save, FIFO self-wake).
+pub(crate) const PWR_CONF: u8 = 0x7C;
+/// Power control (accel/gyro/aux/temperature enable bits).
+pub(crate) const PWR_CTRL: u8 = 0x7D;
+/// Command register.
+pub(crate) const CMD: u8 = 0x7E;
+
+/// Command register value that triggers a Fast Offset Compensation run.
+pub(crate) const CMD_START_FOC: u8 = 0x03;
+
+/// `STATUS` bit set once the command decoder is ready for a new command;
+/// clear while a previously issued `CMD` (e.g. Fast Offset Compensation) is
+/// still being processed.
+pub(crate) const STATUS_CMD_RDY: u8 = 0b0000_1000;
+/// `STATUS` bit set while an aux manual-mode transaction is in flight.
+pub(crate) const STATUS_AUX_BUSY: u8 = 0b0001_0000;
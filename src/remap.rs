@@ -0,0 +1,122 @@
+//! Axis remapping, both the hardware feature-engine remap and a pure-Rust helper.
+
+use crate::interface::{ReadData, WriteData};
+use crate::types::{AxisData, Error};
+use crate::{regs, Bmi270};
+
+/// One physical axis of the sensor.
+pub enum PhysicalAxis {
+    /// Physical X axis.
+    X,
+    /// Physical Y axis.
+    Y,
+    /// Physical Z axis.
+    Z,
+}
+
+/// Maps one logical axis to a physical axis, plus whether it should be inverted.
+pub struct AxisMapping {
+    /// Physical axis the logical axis reads from.
+    pub source: PhysicalAxis,
+    /// Whether the physical axis reading should be negated.
+    pub invert: bool,
+}
+
+/// Board orientation, expressed as where each logical X/Y/Z axis is sourced from.
+///
+/// Mirrors the rotation matrices board files carry to bring a sensor into a
+/// standard reference frame, but expressed as the BMI270's axis-remap
+/// feature registers understand: one source axis and sign per logical axis.
+pub struct AxesRemap {
+    /// Logical X axis mapping.
+    pub x: AxisMapping,
+    /// Logical Y axis mapping.
+    pub y: AxisMapping,
+    /// Logical Z axis mapping.
+    pub z: AxisMapping,
+}
+
+fn axis_bits(axis: &PhysicalAxis) -> u8 {
+    match axis {
+        PhysicalAxis::X => 0b00,
+        PhysicalAxis::Y => 0b01,
+        PhysicalAxis::Z => 0b10,
+    }
+}
+
+fn mapping_bits(mapping: &AxisMapping) -> u8 {
+    axis_bits(&mapping.source) | if mapping.invert { 0b100 } else { 0 }
+}
+
+impl<DI, CommE, CsE> Bmi270<DI>
+where
+    DI: ReadData<Error = Error<CommE, CsE>> + WriteData<Error = Error<CommE, CsE>>,
+{
+    /// Program the hardware axis-remap feature so accel/gyro/aux output is
+    /// already reported in the board's logical reference frame.
+    ///
+    /// Axis remap lives on its own feature-engine page rather than a flat
+    /// register, so this brings that page into the `FEATURES` window before
+    /// writing it.
+    pub fn set_axes_remap(&mut self, remap: AxesRemap) -> Result<(), Error<CommE, CsE>> {
+        let byte = mapping_bits(&remap.x) | (mapping_bits(&remap.y) << 3);
+        self.write_feature_byte(regs::PAGE_AXIS_REMAP, 0, byte)?;
+        self.write_feature_byte(regs::PAGE_AXIS_REMAP, 1, mapping_bits(&remap.z))
+    }
+}
+
+/// Apply a 3x3 orientation matrix to a sample in software.
+///
+/// Useful for FIFO-decoded samples, which bypass the feature engine and so
+/// never see the hardware remap above. Each row of `matrix` gives the
+/// integer coefficients (typically -1, 0, or 1) combined with x/y/z to
+/// produce one output axis.
+pub fn apply_remap(matrix: &[[i8; 3]; 3], data: &AxisData) -> AxisData {
+    let v = [i32::from(data.x), i32::from(data.y), i32::from(data.z)];
+    let row = |r: &[i8; 3]| -> i16 {
+        let sum = i32::from(r[0]) * v[0] + i32::from(r[1]) * v[1] + i32::from(r[2]) * v[2];
+        sum as i16
+    };
+    AxisData {
+        x: row(&matrix[0]),
+        y: row(&matrix[1]),
+        z: row(&matrix[2]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mapping_bits_packs_source_axis_and_invert_flag() {
+        let identity = AxisMapping {
+            source: PhysicalAxis::X,
+            invert: false,
+        };
+        assert_eq!(mapping_bits(&identity), 0b000);
+
+        let inverted_z = AxisMapping {
+            source: PhysicalAxis::Z,
+            invert: true,
+        };
+        assert_eq!(mapping_bits(&inverted_z), 0b110);
+    }
+
+    #[test]
+    fn apply_remap_identity_matrix_is_a_no_op() {
+        let identity = [[1, 0, 0], [0, 1, 0], [0, 0, 1]];
+        let data = AxisData { x: 1, y: 2, z: 3 };
+        let out = apply_remap(&identity, &data);
+        assert_eq!((out.x, out.y, out.z), (1, 2, 3));
+    }
+
+    #[test]
+    fn apply_remap_swaps_and_inverts_axes() {
+        // Logical X = physical Y, logical Y = -physical X, logical Z = physical Z.
+        let matrix = [[0, 1, 0], [-1, 0, 0], [0, 0, 1]];
+        let data = AxisData { x: 10, y: 20, z: 30 };
+        let out = apply_remap(&matrix, &data);
+        assert_eq!((out.x, out.y, out.z), (20, -10, 30));
+    }
+}
@@ -0,0 +1,193 @@
+//! Physical-unit scaling for `AxisData`/`Data`, based on the configured
+//! accelerometer and gyroscope range.
+
+use crate::interface::{ReadData, WriteData};
+use crate::types::{AxisData, Error};
+use crate::{regs, Bmi270};
+
+/// Accelerometer full-scale range.
+pub enum AccRange {
+    /// +/-2g.
+    G2,
+    /// +/-4g.
+    G4,
+    /// +/-8g.
+    G8,
+    /// +/-16g.
+    G16,
+}
+
+impl AccRange {
+    pub(crate) fn bits(&self) -> u8 {
+        match self {
+            AccRange::G2 => 0x00,
+            AccRange::G4 => 0x01,
+            AccRange::G8 => 0x02,
+            AccRange::G16 => 0x03,
+        }
+    }
+
+    fn full_scale_g(&self) -> f32 {
+        match self {
+            AccRange::G2 => 2.0,
+            AccRange::G4 => 4.0,
+            AccRange::G8 => 8.0,
+            AccRange::G16 => 16.0,
+        }
+    }
+
+    fn full_scale_mg(&self) -> i32 {
+        match self {
+            AccRange::G2 => 2_000,
+            AccRange::G4 => 4_000,
+            AccRange::G8 => 8_000,
+            AccRange::G16 => 16_000,
+        }
+    }
+}
+
+/// Gyroscope full-scale range.
+pub enum GyrRange {
+    /// +/-2000 dps.
+    Dps2000,
+    /// +/-1000 dps.
+    Dps1000,
+    /// +/-500 dps.
+    Dps500,
+    /// +/-250 dps.
+    Dps250,
+    /// +/-125 dps.
+    Dps125,
+}
+
+impl GyrRange {
+    pub(crate) fn bits(&self) -> u8 {
+        match self {
+            GyrRange::Dps2000 => 0x00,
+            GyrRange::Dps1000 => 0x01,
+            GyrRange::Dps500 => 0x02,
+            GyrRange::Dps250 => 0x03,
+            GyrRange::Dps125 => 0x04,
+        }
+    }
+
+    fn full_scale_dps(&self) -> f32 {
+        match self {
+            GyrRange::Dps2000 => 2_000.0,
+            GyrRange::Dps1000 => 1_000.0,
+            GyrRange::Dps500 => 500.0,
+            GyrRange::Dps250 => 250.0,
+            GyrRange::Dps125 => 125.0,
+        }
+    }
+
+    fn full_scale_mdps(&self) -> i32 {
+        match self {
+            GyrRange::Dps2000 => 2_000_000,
+            GyrRange::Dps1000 => 1_000_000,
+            GyrRange::Dps500 => 500_000,
+            GyrRange::Dps250 => 250_000,
+            GyrRange::Dps125 => 125_000,
+        }
+    }
+}
+
+/// Sensor time LSB, in microseconds (datasheet: 39.0625 us, i.e. 625/16 us).
+const SENSORTIME_US_NUM: u64 = 625;
+const SENSORTIME_US_DEN: u64 = 16;
+
+/// Convert a raw 3-byte `SENSORTIME`/`Data::time` tick count to microseconds.
+pub fn sensor_time_us(ticks: u32) -> u64 {
+    u64::from(ticks) * SENSORTIME_US_NUM / SENSORTIME_US_DEN
+}
+
+impl<DI, CommE, CsE> Bmi270<DI>
+where
+    DI: ReadData<Error = Error<CommE, CsE>> + WriteData<Error = Error<CommE, CsE>>,
+{
+    /// Set the accelerometer full-scale range and remember it for `to_g`/`to_mg`.
+    pub fn set_accel_range(&mut self, range: AccRange) -> Result<(), Error<CommE, CsE>> {
+        self.iface.write_register(regs::ACC_RANGE, range.bits())?;
+        self.acc_range = range;
+        Ok(())
+    }
+
+    /// Set the gyroscope full-scale range and remember it for `to_dps`/`to_mdps`.
+    pub fn set_gyro_range(&mut self, range: GyrRange) -> Result<(), Error<CommE, CsE>> {
+        self.iface.write_register(regs::GYR_RANGE, range.bits())?;
+        self.gyr_range = range;
+        Ok(())
+    }
+
+    /// Convert raw accelerometer counts to g, using the currently configured range.
+    pub fn to_g(&self, d: &AxisData) -> [f32; 3] {
+        let scale = self.acc_range.full_scale_g() / 32_768.0;
+        [d.x as f32 * scale, d.y as f32 * scale, d.z as f32 * scale]
+    }
+
+    /// Convert raw accelerometer counts to milli-g using integer math, for
+    /// targets without an FPU.
+    pub fn to_mg(&self, d: &AxisData) -> [i32; 3] {
+        let full_mg = self.acc_range.full_scale_mg();
+        [
+            mg_from_raw(full_mg, d.x),
+            mg_from_raw(full_mg, d.y),
+            mg_from_raw(full_mg, d.z),
+        ]
+    }
+
+    /// Convert raw gyroscope counts to degrees per second, using the
+    /// currently configured range.
+    pub fn to_dps(&self, d: &AxisData) -> [f32; 3] {
+        let scale = self.gyr_range.full_scale_dps() / 32_768.0;
+        [d.x as f32 * scale, d.y as f32 * scale, d.z as f32 * scale]
+    }
+
+    /// Convert raw gyroscope counts to milli-degrees-per-second using
+    /// integer math, for targets without an FPU.
+    pub fn to_mdps(&self, d: &AxisData) -> [i32; 3] {
+        let full_mdps = self.gyr_range.full_scale_mdps();
+        [
+            mdps_from_raw(full_mdps, d.x),
+            mdps_from_raw(full_mdps, d.y),
+            mdps_from_raw(full_mdps, d.z),
+        ]
+    }
+}
+
+/// Scale one raw accelerometer count to milli-g for the given full-scale range.
+fn mg_from_raw(full_mg: i32, v: i16) -> i32 {
+    (i32::from(v) * full_mg) / 32_768
+}
+
+/// Scale one raw gyroscope count to milli-degrees-per-second for the given
+/// full-scale range.
+///
+/// Computed in `i64`: at the widest range (2000 dps, `full_mdps ==
+/// 2_000_000`) and a full-scale raw reading, the intermediate product
+/// overflows `i32` before the division brings it back into range.
+fn mdps_from_raw(full_mdps: i32, v: i16) -> i32 {
+    ((i64::from(v) * i64::from(full_mdps)) / 32_768) as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mg_from_raw_scales_to_full_scale_at_i16_min() {
+        let full_mg = AccRange::G8.full_scale_mg();
+        assert_eq!(mg_from_raw(full_mg, i16::MIN), -full_mg);
+        assert_eq!(mg_from_raw(full_mg, 0), 0);
+    }
+
+    #[test]
+    fn mdps_from_raw_does_not_overflow_i32_at_widest_range() {
+        let full_mdps = GyrRange::Dps2000.full_scale_mdps();
+        assert_eq!(mdps_from_raw(full_mdps, i16::MIN), -full_mdps);
+        assert_eq!(
+            mdps_from_raw(full_mdps, i16::MAX),
+            (i64::from(i16::MAX) * i64::from(full_mdps) / 32_768) as i32
+        );
+    }
+}
@@ -4,6 +4,8 @@ pub enum Error<CommE, CsE> {
     Comm(CommE),
     /// Pin error on the SPI chip select.
     Cs(CsE),
+    /// The device did not report completion of an asynchronous operation (FOC, aux transfer) in time.
+    Timeout,
 }
 
 /// Reports sensor error conditions.
@@ -46,9 +48,8 @@ pub struct AxisData {
 pub struct AuxData {
     /// Axis data.
     pub axis: AxisData,
-    // TODO
-    /// Last aux registers data.
-    pub r: i16,
+    /// Trailing raw bytes from the aux burst read, beyond the 6-byte axis payload.
+    pub r: [u8; 2],
 }
 
 /// Sensor data.